@@ -7,6 +7,7 @@
  * of this source tree.
  */
 
+use std::collections::VecDeque;
 use std::time::SystemTime;
 
 use buck2_core::io_counters::IoCounterKey;
@@ -14,31 +15,121 @@ use gazebo::prelude::VecExt;
 use superconsole::DrawMode;
 use superconsole::Line;
 use superconsole::Lines;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::humanized::HumanizedBytes;
 use crate::two_snapshots::TwoSnapshots;
 
+/// Number of past samples kept for the sparklines rendered below the
+/// instantaneous RSS/CPU line.
+const HISTORY_LEN: usize = 60;
+
+/// The eight block-elevation glyphs used to render a sparkline, from lowest
+/// to highest.
+const SPARK_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Clone, Copy, Default)]
+struct HistorySample {
+    rss: Option<f64>,
+    cpu_percent: Option<f64>,
+    deferred_materializer_queue_size: f64,
+    blocking_executor_io_queue_size: f64,
+}
+
 #[derive(Default)]
 pub struct IoState {
     two_snapshots: TwoSnapshots,
+    history: VecDeque<HistorySample>,
+}
+
+/// Normalizes `values` to the min/max of the window and maps each sample to
+/// one of the eight block-elevation glyphs. Returns an empty string if there
+/// are fewer than two samples (nothing to show a trend for).
+fn sparkline(values: impl Iterator<Item = f64> + Clone) -> String {
+    if values.clone().nth(1).is_none() {
+        return String::new();
+    }
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.clone().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .map(|v| {
+            let bucket = if range <= f64::EPSILON {
+                0
+            } else {
+                (((v - min) / range) * (SPARK_GLYPHS.len() - 1) as f64).floor() as usize
+            };
+            SPARK_GLYPHS[bucket.min(SPARK_GLYPHS.len() - 1)]
+        })
+        .collect()
 }
 
-/// Place space-separated words on lines.
+/// Display width of `s`, measured in terminal columns rather than bytes, so
+/// CJK/emoji graphemes count as 2 and combining/zero-width marks count as 0.
+fn display_width(s: &str) -> usize {
+    s.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
+
+/// Split `word` into chunks that each fit within `width` display columns,
+/// breaking at grapheme cluster boundaries. A single grapheme wider than
+/// `width` is not split and forms a chunk of its own.
+fn hard_break(word: &str, width: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for g in word.graphemes(true) {
+        let g_width = UnicodeWidthStr::width(g);
+        if current_width > 0 && current_width + g_width > width {
+            chunks.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current.push_str(g);
+        current_width += g_width;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Place space-separated words on lines, measuring line length by display
+/// width (not byte count) so non-ASCII words wrap correctly.
 fn words_to_lines(words: Vec<String>, width: usize) -> Vec<String> {
     let mut lines = Vec::new();
     let mut current_line = String::new();
+    let mut current_width = 0;
     for word in words {
+        let word_width = display_width(&word);
         if current_line.is_empty() {
-            current_line = word;
+            if word_width > width {
+                let mut chunks = hard_break(&word, width);
+                let last = chunks.pop().expect("hard_break never returns empty");
+                lines.extend(chunks);
+                current_width = display_width(&last);
+                current_line = last;
+            } else {
+                current_width = word_width;
+                current_line = word;
+            }
             continue;
         }
-        // This works correctly only for ASCII strings.
-        if current_line.len() + 1 + word.len() > width {
-            lines.push(current_line);
-            current_line = word;
+        if current_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut current_line));
+            if word_width > width {
+                let mut chunks = hard_break(&word, width);
+                let last = chunks.pop().expect("hard_break never returns empty");
+                lines.extend(chunks);
+                current_width = display_width(&last);
+                current_line = last;
+            } else {
+                current_width = word_width;
+                current_line = word;
+            }
         } else {
             current_line.push(' ');
             current_line.push_str(&word);
+            current_width += 1 + word_width;
         }
     }
     if !current_line.is_empty() {
@@ -81,6 +172,72 @@ pub fn io_in_flight_non_zero_counters(
 impl IoState {
     pub fn update(&mut self, timestamp: SystemTime, snapshot: &buck2_data::Snapshot) {
         self.two_snapshots.update(timestamp, snapshot);
+
+        self.history.push_back(HistorySample {
+            rss: snapshot.buck2_rss.map(|rss| rss as f64),
+            cpu_percent: self.two_snapshots.cpu_percents().map(|cpu| cpu as f64),
+            deferred_materializer_queue_size: snapshot.deferred_materializer_queue_size as f64,
+            blocking_executor_io_queue_size: snapshot.blocking_executor_io_queue_size as f64,
+        });
+        if self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+        }
+    }
+
+    /// Renders a labeled sparkline for each history field with at least two
+    /// non-empty samples, so there's something to show a trend for. The DM
+    /// Queue and IO Queue sparklines are additionally gated on the latest
+    /// sample being non-zero, matching the instantaneous line below so idle
+    /// builds don't grow two permanently-flat sparklines.
+    fn render_history(&self) -> anyhow::Result<Vec<Line>> {
+        let mut lines = Vec::new();
+
+        let mut push = |label: &str, values: Vec<f64>| -> anyhow::Result<()> {
+            if values.len() < 2 {
+                return Ok(());
+            }
+            lines.push(Line::from_iter([superconsole::Span::new_unstyled(
+                format!("{} {}", label, sparkline(values.into_iter())),
+            )?]));
+            Ok(())
+        };
+
+        push(
+            "RSS     ",
+            self.history.iter().filter_map(|s| s.rss).collect(),
+        )?;
+        push(
+            "CPU     ",
+            self.history.iter().filter_map(|s| s.cpu_percent).collect(),
+        )?;
+        if self
+            .history
+            .back()
+            .is_some_and(|s| s.deferred_materializer_queue_size > 0.0)
+        {
+            push(
+                "DM Queue",
+                self.history
+                    .iter()
+                    .map(|s| s.deferred_materializer_queue_size)
+                    .collect(),
+            )?;
+        }
+        if self
+            .history
+            .back()
+            .is_some_and(|s| s.blocking_executor_io_queue_size > 0.0)
+        {
+            push(
+                "IO Queue",
+                self.history
+                    .iter()
+                    .map(|s| s.blocking_executor_io_queue_size)
+                    .collect(),
+            )?;
+        }
+
+        Ok(lines)
     }
 
     fn do_render(&self, snapshot: &buck2_data::Snapshot, width: usize) -> anyhow::Result<Lines> {
@@ -110,6 +267,8 @@ impl IoState {
             )?]));
         }
 
+        lines.extend(self.render_history()?);
+
         let mut counters = Vec::new();
         for (key, value) in io_in_flight_non_zero_counters(snapshot) {
             counters.push(format!("{:?} = {}", key, value));
@@ -141,6 +300,7 @@ impl IoState {
 
 #[cfg(test)]
 mod tests {
+    use super::sparkline;
     use super::words_to_lines;
 
     #[test]
@@ -163,4 +323,42 @@ mod tests {
             words_to_lines(vec!["abcd".to_owned()], 3)
         );
     }
+
+    #[test]
+    fn test_words_to_lines_wide_chars() {
+        // Each CJK character has display width 2, so "你好" is 4 columns wide.
+        assert_eq!(
+            vec!["你好".to_owned()],
+            words_to_lines(vec!["你好".to_owned()], 4)
+        );
+        assert_eq!(
+            vec!["你".to_owned(), "好".to_owned()],
+            words_to_lines(vec!["你".to_owned(), "好".to_owned()], 3)
+        );
+        // A single wide word that doesn't fit at all is hard-broken at
+        // grapheme boundaries rather than emitting an over-long line.
+        assert_eq!(
+            vec!["你好".to_owned(), "吗".to_owned()],
+            words_to_lines(vec!["你好吗".to_owned()], 4)
+        );
+    }
+
+    #[test]
+    fn test_words_to_lines_combining_chars() {
+        // "e" + combining acute accent (U+0301) forms one grapheme of
+        // display width 1, not 2.
+        let e_acute = "e\u{0301}";
+        assert_eq!(
+            vec![format!("{e_acute} ab")],
+            words_to_lines(vec![e_acute.to_owned(), "ab".to_owned()], 5)
+        );
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!("▁█", sparkline([0.0, 10.0].into_iter()));
+        assert_eq!("▁▄█", sparkline([0.0, 5.0, 10.0].into_iter()));
+        // A flat window has no trend to show: everything bottoms out.
+        assert_eq!("▁▁▁", sparkline([5.0, 5.0, 5.0].into_iter()));
+    }
 }
@@ -0,0 +1,93 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+//!
+//! Serializable representation of a DICE computation graph, used for
+//! introspection, debugging, and graph dumps. Both the legacy and modern
+//! engines produce the same node shape so that callers (and the `dot`/JSON
+//! export in `introspect`) don't need to care which engine computed them.
+
+use allocative::Allocative;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::Serializer;
+
+/// A type-erased, displayable representation of a computed key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Allocative)]
+pub struct AnyKey {
+    pub key_type: String,
+    pub key: String,
+}
+
+impl AnyKey {
+    pub fn new(key_type: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            key_type: key_type.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for AnyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key)
+    }
+}
+
+/// One computed node in a DICE graph, in the dense form used for the
+/// bincode export. `deps`/this node's own id are indices into the full node
+/// list handed to `serialize_dense_graph`/`serialize_graph`.
+#[derive(Debug, Clone, Serialize, Deserialize, Allocative)]
+pub struct SerializedGraphNodesForKey {
+    pub id: usize,
+    pub key_type: String,
+    pub key: String,
+    pub version: usize,
+    pub deps: Vec<usize>,
+    pub currently_running: bool,
+}
+
+/// Introspectable state of the legacy engine: the flat list of its computed
+/// nodes, one entry per node.
+#[derive(Clone, Allocative)]
+pub struct LegacyIntrospectable(pub Vec<SerializedGraphNodesForKey>);
+
+/// The DICE computation graph, as produced by whichever engine
+/// (`DiceImplementation::Legacy` or `DiceImplementation::Modern`) is active.
+#[derive(Clone, Allocative)]
+pub enum GraphIntrospectable {
+    Legacy {
+        introspectables: LegacyIntrospectable,
+    },
+    Modern {
+        nodes: Vec<SerializedGraphNodesForKey>,
+    },
+}
+
+impl GraphIntrospectable {
+    /// All nodes in this graph, regardless of which engine produced them.
+    pub fn nodes(&self) -> &[SerializedGraphNodesForKey] {
+        match self {
+            GraphIntrospectable::Legacy { introspectables } => &introspectables.0,
+            GraphIntrospectable::Modern { nodes } => nodes,
+        }
+    }
+}
+
+/// Serializes as the flat dense node list (`SerializedGraphNodesForKey`),
+/// independent of which engine variant produced it, so the bincode export
+/// has one stable schema.
+impl Serialize for GraphIntrospectable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.nodes().serialize(serializer)
+    }
+}
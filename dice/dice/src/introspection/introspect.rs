@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+//!
+//! Serialization of a `GraphIntrospectable` to the various formats consumed
+//! by `buck2 debug`: a brittle but dependency-free tab-separated node/edge
+//! stream, a dense bincode export of the same nodes, and the
+//! visualization-friendly `dot`/JSON exports below.
+
+use std::io;
+use std::io::Write;
+
+use crate::introspection::graph::AnyKey;
+use crate::introspection::graph::GraphIntrospectable;
+use crate::introspection::graph::SerializedGraphNodesForKey;
+
+/// Writes `graph` as a tab-separated node stream (`idx\tkey_type\tkey`) and a
+/// tab-separated edge stream (`idx\tidx`), and reports which keys are
+/// currently being computed.
+pub fn serialize_graph(
+    graph: &GraphIntrospectable,
+    nodes: &mut Vec<u8>,
+    edges: &mut Vec<u8>,
+    nodes_currently_running: &mut Vec<AnyKey>,
+) -> io::Result<()> {
+    for node in graph.nodes() {
+        writeln!(nodes, "{}\t{}\t{}", node.id, node.key_type, node.key)?;
+        for dep in &node.deps {
+            writeln!(edges, "{}\t{}", node.id, dep)?;
+        }
+        if node.currently_running {
+            nodes_currently_running.push(AnyKey::new(node.key_type.clone(), node.key.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the dense, bincode-friendly form of `graph`'s nodes. This is the
+/// same data `GraphIntrospectable`'s own `Serialize` impl produces, exposed
+/// as a plain function for callers that only want the nodes.
+pub fn serialize_dense_graph(graph: &GraphIntrospectable) -> Vec<SerializedGraphNodesForKey> {
+    graph.nodes().to_vec()
+}
+
+/// Writes `graph` as a Graphviz `digraph`, suitable for piping straight into
+/// `dot`/`gephi`. Nodes currently being computed are styled distinctly
+/// (`style=filled`) so fan-out from an in-flight build is easy to spot.
+pub fn serialize_graph_dot(graph: &GraphIntrospectable, out: &mut Vec<u8>) -> io::Result<()> {
+    writeln!(out, "digraph dice {{")?;
+    for node in graph.nodes() {
+        let label = format!(
+            "{}\\n{}",
+            escape_dot_label(&node.key_type),
+            escape_dot_label(&node.key)
+        );
+        if node.currently_running {
+            writeln!(
+                out,
+                "  {} [label=\"{}\", style=filled, fillcolor=lightblue];",
+                node.id, label
+            )?;
+        } else {
+            writeln!(out, "  {} [label=\"{}\"];", node.id, label)?;
+        }
+    }
+    for node in graph.nodes() {
+        for dep in &node.deps {
+            writeln!(out, "  {} -> {};", node.id, dep)?;
+        }
+    }
+    writeln!(out, "}}")?;
+
+    Ok(())
+}
+
+fn escape_dot_label(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Schema-stable JSON form of `graph`, for tools that don't want to hand-roll
+/// a parser for the tab-separated node/edge streams `serialize_graph` emits.
+/// Emitted as a bare array of `SerializedGraphNodesForKey` (the same shape as
+/// the bincode dense export) rather than a wrapper object, so callers can
+/// deserialize straight into `Vec<SerializedGraphNodesForKey>`.
+pub fn serialize_graph_json(graph: &GraphIntrospectable) -> serde_json::Result<String> {
+    serde_json::to_string(graph.nodes())
+}
@@ -13,6 +13,7 @@
 use crate::introspection::graph::AnyKey;
 use crate::introspection::graph::GraphIntrospectable;
 use crate::introspection::graph::LegacyIntrospectable;
+use crate::introspection::graph::SerializedGraphNodesForKey;
 use crate::Dice;
 use crate::DiceImplementation;
 
@@ -21,15 +22,16 @@ pub(crate) mod introspect;
 
 pub use crate::introspection::introspect::serialize_dense_graph;
 pub use crate::introspection::introspect::serialize_graph;
+pub use crate::introspection::introspect::serialize_graph_dot;
+pub use crate::introspection::introspect::serialize_graph_json;
+use crate::impls::dice::DiceModern;
 use crate::legacy::DiceLegacy;
 
 impl Dice {
     pub fn to_introspectable(&self) -> GraphIntrospectable {
         match &self.implementation {
             DiceImplementation::Legacy(dice) => dice.to_introspectable(),
-            DiceImplementation::Modern(_) => {
-                unimplemented!("todo")
-            }
+            DiceImplementation::Modern(dice) => dice.to_introspectable(),
         }
     }
 }
@@ -42,6 +44,57 @@ impl DiceLegacy {
     }
 }
 
+impl DiceModern {
+    pub fn to_introspectable(&self) -> GraphIntrospectable {
+        let state = self.core.read();
+
+        // Two nodes can share the same `(key_type, key)` display string at
+        // different versions (that's the whole reason `SerializedGraphNodesForKey`
+        // carries a `version` field), so the id map must be keyed on version
+        // too, not just the key's display form, or distinct nodes collapse
+        // onto the same id and dependency edges point at the wrong version.
+        let ids: std::collections::HashMap<(AnyKey, usize), usize> = state
+            .keys()
+            .map(|key| {
+                (
+                    AnyKey::new(key.key_type_name(), format!("{}", key)),
+                    state.version(key).to_usize(),
+                )
+            })
+            .enumerate()
+            .map(|(idx, key)| (key, idx))
+            .collect();
+
+        let nodes = state
+            .keys()
+            .map(|key| {
+                let any_key = AnyKey::new(key.key_type_name(), format!("{}", key));
+                let version = state.version(key).to_usize();
+                let id = ids[&(any_key.clone(), version)];
+                let deps = state
+                    .deps(key)
+                    .map(|dep| {
+                        let dep_key = AnyKey::new(dep.key_type_name(), format!("{}", dep));
+                        let dep_version = state.version(dep).to_usize();
+                        ids[&(dep_key, dep_version)]
+                    })
+                    .collect();
+
+                SerializedGraphNodesForKey {
+                    id,
+                    key_type: any_key.key_type,
+                    key: any_key.key,
+                    version,
+                    deps,
+                    currently_running: state.is_running(key),
+                }
+            })
+            .collect();
+
+        GraphIntrospectable::Modern { nodes }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use allocative::Allocative;
@@ -54,12 +107,43 @@ mod tests {
     use crate::api::computations::DiceComputations;
     use crate::api::cycles::DetectCycles;
     use crate::api::key::Key;
+    use crate::introspection::graph::GraphIntrospectable;
     use crate::introspection::graph::SerializedGraphNodesForKey;
     use crate::introspection::serialize_graph;
+    use crate::introspection::serialize_graph_dot;
+    use crate::introspection::serialize_graph_json;
+    use crate::impls::dice::DiceModern;
     use crate::DiceLegacy;
     use crate::HashMap;
     use crate::WhichSpawner;
 
+    /// Which engine to build and exercise; both are expected to produce the
+    /// same `KeyA(n) -> KeyA(n-1) -> KeyB` edge chain.
+    #[derive(Copy, Clone, Debug)]
+    enum WhichDice {
+        Legacy,
+        Modern,
+    }
+
+    async fn compute_introspectable(which: WhichDice) -> anyhow::Result<GraphIntrospectable> {
+        Ok(match which {
+            WhichDice::Legacy => {
+                let dice = DiceLegacy::builder()
+                    .build(DetectCycles::Disabled, WhichSpawner::ExplicitCancel);
+                let ctx = dice.updater().commit().await;
+                ctx.compute(&KeyA(3)).await?;
+                dice.to_introspectable()
+            }
+            WhichDice::Modern => {
+                let dice = DiceModern::builder()
+                    .build(DetectCycles::Disabled, WhichSpawner::ExplicitCancel);
+                let ctx = dice.updater().commit().await;
+                ctx.compute(&KeyA(3)).await?;
+                dice.to_introspectable()
+            }
+        })
+    }
+
     #[derive(Clone, Dupe, Display, Debug, Eq, Hash, PartialEq, Allocative)]
     #[display(fmt = "{:?}", self)]
     struct KeyA(usize);
@@ -106,19 +190,15 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn test_serialization() -> anyhow::Result<()> {
-        let dice =
-            DiceLegacy::builder().build(DetectCycles::Disabled, WhichSpawner::ExplicitCancel);
-        let ctx = dice.updater().commit().await;
-        ctx.compute(&KeyA(3)).await?;
+    async fn assert_serialization(which: WhichDice) -> anyhow::Result<()> {
+        let introspectable = compute_introspectable(which).await?;
 
         let mut nodes = Vec::new();
         let mut edges = Vec::new();
         let mut nodes_currently_running = Vec::new();
 
         serialize_graph(
-            &dice.to_introspectable(),
+            &introspectable,
             &mut nodes,
             &mut edges,
             &mut nodes_currently_running,
@@ -162,15 +242,63 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_serialization_dense() -> anyhow::Result<()> {
-        let dice =
-            DiceLegacy::builder().build(DetectCycles::Disabled, WhichSpawner::ExplicitCancel);
-        let ctx = dice.updater().commit().await;
-        ctx.compute(&KeyA(3)).await?;
+    async fn test_serialization_legacy() -> anyhow::Result<()> {
+        assert_serialization(WhichDice::Legacy).await
+    }
+
+    #[tokio::test]
+    async fn test_serialization_modern() -> anyhow::Result<()> {
+        assert_serialization(WhichDice::Modern).await
+    }
+
+    async fn assert_serialization_dense(which: WhichDice) -> anyhow::Result<()> {
+        let introspectable = compute_introspectable(which).await?;
 
-        let node = bincode::serialize(&dice.to_introspectable())?;
+        let node = bincode::serialize(&introspectable)?;
 
         let _out: Vec<SerializedGraphNodesForKey> = bincode::deserialize(&node)?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_serialization_dense_legacy() -> anyhow::Result<()> {
+        assert_serialization_dense(WhichDice::Legacy).await
+    }
+
+    #[tokio::test]
+    async fn test_serialization_dense_modern() -> anyhow::Result<()> {
+        assert_serialization_dense(WhichDice::Modern).await
+    }
+
+    #[tokio::test]
+    async fn test_serialize_graph_dot() -> anyhow::Result<()> {
+        let introspectable = compute_introspectable(WhichDice::Legacy).await?;
+
+        let mut dot = Vec::new();
+        serialize_graph_dot(&introspectable, &mut dot).unwrap();
+        let dot = String::from_utf8(dot)?;
+
+        assert!(dot.starts_with("digraph dice {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert!(dot.contains("KeyB"));
+        // 4 node declarations (KeyA(3..0)) + 1 for KeyB, plus 4 edges.
+        assert_eq!(5, dot.matches("label=").count());
+        assert_eq!(4, dot.matches("->").count());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serialize_graph_json() -> anyhow::Result<()> {
+        let introspectable = compute_introspectable(WhichDice::Legacy).await?;
+
+        let json = serialize_graph_json(&introspectable)?;
+        let nodes: Vec<SerializedGraphNodesForKey> =
+            serde_json::from_str(&json).context("invalid JSON graph export")?;
+
+        assert_eq!(5, nodes.len());
+        assert!(nodes.iter().any(|n| n.key == "KeyB"));
+
+        Ok(())
+    }
 }
@@ -62,6 +62,15 @@ where
             unreachable!("validated at construction")
         }
     }
+
+    /// Unpack the mutable branch only: `Some` if this value is the still
+    /// mutable `T`, `None` if it has been frozen to `T::Frozen`. Lets native
+    /// functions mutate the value in place instead of only reading it via
+    /// [`unpack`](Self::unpack).
+    #[inline]
+    pub fn downcast_mut(self) -> anyhow::Result<Option<&'v mut T>> {
+        self.0.downcast_mut::<T>()
+    }
 }
 
 impl<'v, T> StarlarkTypeRepr for ValueOfComplex<'v, T>
@@ -101,6 +110,16 @@ where
             None
         }
     }
+
+    fn unpack_value_err(value: Value<'v>) -> anyhow::Result<Self> {
+        Self::unpack_value(value).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Expected `{}`, got value of type `{}`",
+                T::starlark_type_repr(),
+                value.get_type()
+            )
+        })
+    }
 }
 
 #[cfg(test)]
@@ -120,7 +139,11 @@ mod tests {
     use crate::environment::GlobalsBuilder;
     use crate::values::layout::complex::ValueOfComplex;
     use crate::values::starlark_value;
+    use crate::values::type_repr::StarlarkTypeRepr;
+    use crate::values::FrozenHeap;
+    use crate::values::Heap;
     use crate::values::StarlarkValue;
+    use crate::values::UnpackValue;
     use crate::values::Value;
     use crate::values::ValueLike;
 
@@ -169,4 +192,43 @@ mod tests {
         a.eq("'test1'", "test_unpack(x)");
         a.eq("'test2'", "test_unpack(y)");
     }
+
+    #[test]
+    fn test_unpack_value_err_message() {
+        let heap = Heap::new();
+        let wrong_type = heap.alloc("not a TestValueOfComplex");
+        let err =
+            ValueOfComplex::<TestValueOfComplex<Value>>::unpack_value_err(wrong_type).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains(&*TestValueOfComplex::<Value>::starlark_type_repr().to_string()),
+            "{message}"
+        );
+        assert!(message.contains(wrong_type.get_type()), "{message}");
+    }
+
+    #[test]
+    fn test_downcast_mut() {
+        let heap = Heap::new();
+        let v = heap.alloc_complex(TestValueOfComplex(heap.alloc("before")));
+        let v = ValueOfComplex::<TestValueOfComplex<Value>>::unpack_value(v).unwrap();
+
+        v.downcast_mut().unwrap().expect("not frozen").0 = heap.alloc("after");
+
+        match v.unpack() {
+            Either::Left(v) => assert_eq!(Some("after"), v.0.unpack_str()),
+            Either::Right(_) => panic!("value should still be mutable"),
+        }
+    }
+
+    #[test]
+    fn test_downcast_mut_frozen() {
+        let frozen_heap = FrozenHeap::new();
+        let v = frozen_heap.alloc_simple(TestValueOfComplex(
+            const_frozen_string!("frozen").to_frozen_value(),
+        ));
+        let v = ValueOfComplex::<TestValueOfComplex<Value>>::unpack_value(v.to_value()).unwrap();
+
+        assert!(v.downcast_mut().unwrap().is_none());
+    }
 }